@@ -39,8 +39,7 @@ async fn main() {
 
     loop {
         set_camera(&render_target_cam);
-        clear_background(BLUE);
-        res.draw_level(level, &tilesets, Vec2::new(0.0, 0.0), None);
+        res.draw_level(level, &tilesets, Vec2::new(0.0, 0.0), None, true);
 
         // Draw coins
         for c in &coins {
@@ -7,6 +7,7 @@ pub enum Error {
     SerdeJson(serde_json::error::Category),
     LayerTypeNotFound { layer_type: String },
     NullWorldType,
+    ExternalLevelMissing { path: String },
 }
 
 impl std::error::Error for Error {}
@@ -19,7 +20,10 @@ impl Display for Error {
             Self::LayerTypeNotFound { layer_type } => write!(
                 f, "Invalid layer type: {}. This should not happen unless the leveldata was modified outside LDtk.", layer_type
             ),
-            Self::NullWorldType => write!(f, "Null world types are unsupported in this version of the library.")
+            Self::NullWorldType => write!(f, "Null world types are unsupported in this version of the library."),
+            Self::ExternalLevelMissing { path } => write!(
+                f, "Could not load external level file at \"{}\". Check that the project's external levels are present and valid.", path
+            ),
         }
     }
 }
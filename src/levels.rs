@@ -1,5 +1,7 @@
 //! Module to handle interactions with the levels
 
+use std::collections::HashSet;
+
 use macroquad::prelude::*;
 
 use crate::types::{LdtkEntityInstance, LdtkLayerType, LdtkLevel, LdtkResources};
@@ -7,6 +9,8 @@ use crate::types::{LdtkEntityInstance, LdtkLayerType, LdtkLevel, LdtkResources};
 impl LdtkResources {
     /// Draws the specified level. The texture array passed in should be the same as when the project was initially loaded.
     /// The `source` rect is in grid coordinates, while the `position` vector is in pixel coordinates.
+    /// When `draw_bg` is `true`, the level's background color is filled behind the layers,
+    /// so callers don't need to hardcode their own `clear_background`.
     ///
     /// `textures` *must* be the same array as was passed in when the project was loaded.
     pub fn draw_level(
@@ -15,6 +19,7 @@ impl LdtkResources {
         textures: &[(Texture2D, &str)],
         position: Vec2,
         source: Option<Rect>,
+        draw_bg: bool,
     ) {
         let lvl = &self
             .levels
@@ -22,6 +27,16 @@ impl LdtkResources {
             .expect(format!("No level at coordinate {:?}", level_coord).as_str()); // I feel a panic is good enough here.
         let tilesets = &self.tilesets;
 
+        if draw_bg {
+            draw_rectangle(
+                position.x,
+                position.y,
+                lvl.width as f32,
+                lvl.height as f32,
+                lvl.bg_color,
+            );
+        }
+
         for layer in &lvl.layers {
             let layerdef = &self.layer_defs.get(&layer.layerdef_id).unwrap();
 
@@ -29,8 +44,60 @@ impl LdtkResources {
                 continue; // Skip non displayable layers
             }
 
+            // IntGrid cells with no tile fall back to a solid color from the layer's legend,
+            // matching the editor preview. Drawn first so tiles layer on top of them.
+            if !layerdef.int_grid_colors.is_empty() {
+                let tiled_cells: HashSet<(i64, i64)> = layer
+                    .tiles
+                    .iter()
+                    .map(|t| {
+                        (
+                            t.px_coords[0] / layer.grid_size,
+                            t.px_coords[1] / layer.grid_size,
+                        )
+                    })
+                    .collect();
+
+                for (i, val) in layer.int_grid_values.iter().enumerate() {
+                    let color = match layerdef.int_grid_colors.get(val) {
+                        Some(c) => c,
+                        None => continue,
+                    };
+
+                    let grid_x = i as i64 % layer.grid_width;
+                    let grid_y = i as i64 / layer.grid_width;
+
+                    if tiled_cells.contains(&(grid_x, grid_y)) {
+                        continue; // A tile already covers this cell
+                    }
+
+                    if let Some(s) = source {
+                        if grid_x < s.x as i64
+                            || grid_x >= (s.x + s.w) as i64
+                            || grid_y < s.y as i64
+                            || grid_y >= (s.y + s.h) as i64
+                        {
+                            continue;
+                        }
+                    }
+
+                    draw_rectangle(
+                        (grid_x * layer.grid_size) as f32 + position.x,
+                        (grid_y * layer.grid_size) as f32 + position.y,
+                        layer.grid_size as f32,
+                        layer.grid_size as f32,
+                        Color::new(
+                            color[0] as f32 / 255.0,
+                            color[1] as f32 / 255.0,
+                            color[2] as f32 / 255.0,
+                            (color[3] as f32 / 255.0) * layerdef.opacity as f32,
+                        ),
+                    );
+                }
+            }
+
             if layer.tileset_id.is_none() {
-                continue; // This layer has nothing to render
+                continue; // This layer has nothing left to render
             }
             let tileset_id = layer.tileset_id.as_ref().unwrap();
 
@@ -59,6 +126,8 @@ impl LdtkResources {
                             w: tileset.tile_grid_size as f32,
                             h: tileset.tile_grid_size as f32,
                         }),
+                        flip_x: t.flip.x,
+                        flip_y: t.flip.y,
                         ..Default::default()
                     },
                 );
@@ -66,6 +135,57 @@ impl LdtkResources {
         }
     }
 
+    /// Draws every level at its true world-space pixel origin, so adjacent rooms line up without
+    /// hand-computed offsets. `camera_rect`, if given, is in world pixel coordinates and culls any
+    /// level whose bounds don't overlap it.
+    pub fn draw_world(&self, textures: &[(Texture2D, &str)], camera_rect: Option<Rect>, draw_bg: bool) {
+        for (coord, level) in &self.levels {
+            let level_rect = Rect::new(
+                level.world_x as f32,
+                level.world_y as f32,
+                level.width as f32,
+                level.height as f32,
+            );
+
+            if let Some(cam) = camera_rect {
+                if !level_rect.overlaps(&cam) {
+                    continue;
+                }
+            }
+
+            self.draw_level(
+                *coord,
+                textures,
+                Vec2::new(level.world_x as f32, level.world_y as f32),
+                None,
+                draw_bg,
+            );
+        }
+    }
+
+    /// Gets the level coordinates of every level touching the given level's edges, per the
+    /// editor's `__neighbours` data. Useful for streaming in just the current room plus its
+    /// neighbors instead of the whole world.
+    pub fn neighbors(&self, level_coord: (i64, i64)) -> Vec<(i64, i64)> {
+        let level = match self.levels.get(&level_coord) {
+            Some(level) => level,
+            None => return Vec::new(),
+        };
+
+        let mut coords = Vec::new();
+        for iid in &level.neighbour_iids {
+            let Some((coord, _)) = self.levels.iter().find(|(_, l)| &l.iid == iid) else {
+                continue;
+            };
+
+            if !coords.contains(coord) {
+                coords.push(*coord);
+            }
+        }
+
+        coords
+    }
+
     /// Gets all entities in a specified level. Useful for spawning entities on load.
     pub fn get_entities(&self, level_coord: (i64, i64)) -> Vec<&LdtkEntityInstance> {
         let mut entities = Vec::new();
@@ -106,9 +226,66 @@ impl LdtkLevel {
 
         rects
     }
+
+    /// Like [`LdtkLevel::generate_collision_rects`], but greedily merges runs of matching cells
+    /// into maximal rectangles instead of emitting one per cell. Produces far fewer colliders on
+    /// large levels while covering exactly the same cells.
+    pub fn generate_merged_collision_rects(&self, layer_idx: usize, target_value: i64) -> Vec<Rect> {
+        let layer = &self.layers[layer_idx];
+        let width = layer.grid_width;
+        let height = layer.grid_height;
+
+        let matches = |x: i64, y: i64| layer.int_grid_values[(y * width + x) as usize] == target_value;
+
+        let mut consumed = vec![false; layer.int_grid_values.len()];
+        let mut rects = Vec::new();
+
+        for y in 0..height {
+            for x in 0..width {
+                if consumed[(y * width + x) as usize] || !matches(x, y) {
+                    continue;
+                }
+
+                // Extend rightward along the row to find the run width.
+                let mut w = 1;
+                while x + w < width && !consumed[(y * width + x + w) as usize] && matches(x + w, y) {
+                    w += 1;
+                }
+
+                // Extend downward, accepting a new row only if all `w` cells in it match and are
+                // unconsumed.
+                let mut h = 1;
+                'rows: while y + h < height {
+                    for dx in 0..w {
+                        let cell = ((y + h) * width + x + dx) as usize;
+                        if consumed[cell] || !matches(x + dx, y + h) {
+                            break 'rows;
+                        }
+                    }
+                    h += 1;
+                }
+
+                for dy in 0..h {
+                    for dx in 0..w {
+                        consumed[((y + dy) * width + x + dx) as usize] = true;
+                    }
+                }
+
+                rects.push(Rect::new(
+                    (x * layer.grid_size) as f32,
+                    (y * layer.grid_size) as f32,
+                    (w * layer.grid_size) as f32,
+                    (h * layer.grid_size) as f32,
+                ));
+            }
+        }
+
+        rects
+    }
 }
 
 mod test {
+    use macroquad::color::BLACK;
     use macroquad::math::Rect;
 
     use crate::types::{LdtkLayerInstance, LdtkLevel};
@@ -129,6 +306,12 @@ mod test {
             width: 4,
             height: 3,
             layers: vec![layer],
+            iid: "test".to_owned(),
+            world_x: 0,
+            world_y: 0,
+            neighbour_iids: Vec::new(),
+            bg_color: BLACK,
+            bg_image: None,
         };
 
         let expected = vec![
@@ -141,4 +324,36 @@ mod test {
 
         assert_eq!(level.generate_collision_rects(0, 1), expected);
     }
+
+    #[test]
+    fn merged_rect_generation() {
+        let layer = LdtkLayerInstance {
+            grid_width: 4,
+            grid_height: 3,
+            grid_size: 16,
+            layerdef_id: "No".to_owned(),
+            tileset_id: None,
+            tiles: Vec::new(),
+            entities: Vec::new(),
+            int_grid_values: vec![1, 1, 0, 0, 1, 1, 0, 1, 0, 0, 0, 1],
+        };
+        let level = LdtkLevel {
+            width: 4,
+            height: 3,
+            layers: vec![layer],
+            iid: "test".to_owned(),
+            world_x: 0,
+            world_y: 0,
+            neighbour_iids: Vec::new(),
+            bg_color: BLACK,
+            bg_image: None,
+        };
+
+        let expected = vec![
+            Rect::new(0.0, 0.0, 32.0, 32.0),
+            Rect::new(48.0, 16.0, 16.0, 32.0),
+        ];
+
+        assert_eq!(level.generate_merged_collision_rects(0, 1), expected);
+    }
 }
@@ -1,6 +1,11 @@
 //! Functions to load data from an LDtk project.
 
-use std::{collections::HashMap, fs::File, io::BufReader, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
 
 use convert::{convert_layer_def, convert_level};
 use macroquad::texture::Texture2D;
@@ -60,17 +65,23 @@ pub fn load_project(path: &str, textures: &[(Texture2D, &str)]) -> Result<LdtkRe
     match json.world_layout.unwrap() {
         WorldLayout::Free | WorldLayout::GridVania => {
             for level in &json.levels {
-                levels.insert((level.world_x, level.world_y), convert_level(level));
+                let layer_instances = resolve_layer_instances(level, json.external_levels, &path_base)?;
+                levels.insert(
+                    (level.world_x, level.world_y),
+                    convert_level(level, &layer_instances),
+                );
             }
         }
         WorldLayout::LinearHorizontal => {
             for (i, level) in json.levels.iter().enumerate() {
-                levels.insert((i as i64, 0), convert_level(level));
+                let layer_instances = resolve_layer_instances(level, json.external_levels, &path_base)?;
+                levels.insert((i as i64, 0), convert_level(level, &layer_instances));
             }
         }
         WorldLayout::LinearVertical => {
             for (i, level) in json.levels.iter().enumerate() {
-                levels.insert((0, i as i64), convert_level(level));
+                let layer_instances = resolve_layer_instances(level, json.external_levels, &path_base)?;
+                levels.insert((0, i as i64), convert_level(level, &layer_instances));
             }
         }
     }
@@ -85,6 +96,47 @@ pub fn load_project(path: &str, textures: &[(Texture2D, &str)]) -> Result<LdtkRe
     Ok(resources)
 }
 
+/// Resolves a level's layer instances, loading them from its external `.ldtkl` file
+/// (relative to `path_base`) when the project uses "Save levels to separate files".
+fn resolve_layer_instances(
+    level: &Level,
+    external_levels: bool,
+    path_base: &Path,
+) -> Result<Vec<LayerInstance>, Error> {
+    if let Some(layer_instances) = &level.layer_instances {
+        return Ok(layer_instances.clone());
+    }
+
+    if !external_levels {
+        return Err(Error::ExternalLevelMissing {
+            path: level.identifier.clone(),
+        });
+    }
+
+    let rel_path = level
+        .external_rel_path
+        .as_ref()
+        .ok_or_else(|| Error::ExternalLevelMissing {
+            path: level.identifier.clone(),
+        })?;
+    let full_path = path_base.join(rel_path);
+
+    let file = File::open(&full_path).map_err(|_| Error::ExternalLevelMissing {
+        path: full_path.display().to_string(),
+    })?;
+    let reader = BufReader::new(file);
+    let external_level: Level =
+        serde_json::from_reader(reader).map_err(|_| Error::ExternalLevelMissing {
+            path: full_path.display().to_string(),
+        })?;
+
+    external_level
+        .layer_instances
+        .ok_or_else(|| Error::ExternalLevelMissing {
+            path: full_path.display().to_string(),
+        })
+}
+
 /// Loads the project and gives the raw `serde` output.
 pub fn load_project_raw(path: &str) -> Result<LdtkJson, Error> {
     let file = File::open(path)?;
@@ -97,11 +149,13 @@ pub fn load_project_raw(path: &str) -> Result<LdtkJson, Error> {
 
 /// Internal type conversions mod
 mod convert {
+    use macroquad::color::{Color, BLACK};
+
     use crate::error::Error;
-    use crate::parser::{EntityInstance, LayerDefinition, Level, TileInstance};
+    use crate::parser::{EntityInstance, FieldInstance, LayerDefinition, LayerInstance, Level, TileInstance};
     use crate::types::{
-        LdtkEntityInstance, LdtkLayerDef, LdtkLayerInstance, LdtkLayerType, LdtkLevel,
-        LdtkTileInstance,
+        FieldValue, LdtkEntityInstance, LdtkLayerDef, LdtkLayerInstance, LdtkLayerType, LdtkLevel,
+        LdtkLevelBg, LdtkTileFlip, LdtkTileInstance,
     };
 
     /// Converts a TileInstance into an LdtkTileInstance.
@@ -111,6 +165,10 @@ mod convert {
             px_coords: [input.px[0], input.px[1]],
             src_coords: [input.src[0], input.src[1]],
             tile_id: input.t,
+            flip: LdtkTileFlip {
+                x: input.f & 0b01 != 0,
+                y: input.f & 0b10 != 0,
+            },
         }
     }
 
@@ -139,6 +197,12 @@ mod convert {
             None
         };
 
+        let fields = input
+            .field_instances
+            .iter()
+            .map(|f| (f.identifier.clone(), convert_field_value(f)))
+            .collect();
+
         LdtkEntityInstance {
             grid_coords: [input.grid[0], input.grid[1]],
             pivot: [input.pivot[0], input.pivot[1]],
@@ -149,29 +213,114 @@ mod convert {
             iid: input.iid.clone(),
             height: input.height,
             width: input.width,
+            fields,
+        }
+    }
+
+    /// Converts a FieldInstance into a typed FieldValue, based on its `__type`.
+    pub fn convert_field_value(input: &FieldInstance) -> FieldValue {
+        parse_field_value(&input.field_type, &input.value)
+    }
+
+    fn parse_field_value(field_type: &str, value: &serde_json::Value) -> FieldValue {
+        if let Some(inner_type) = field_type
+            .strip_prefix("Array<")
+            .and_then(|s| s.strip_suffix('>'))
+        {
+            return match value.as_array() {
+                Some(values) => FieldValue::Array(
+                    values
+                        .iter()
+                        .map(|v| parse_field_value(inner_type, v))
+                        .collect(),
+                ),
+                None => FieldValue::Raw(value.clone()),
+            };
+        }
+
+        match field_type {
+            "Int" => value
+                .as_i64()
+                .map(FieldValue::Int)
+                .unwrap_or_else(|| FieldValue::Raw(value.clone())),
+            "Float" => value
+                .as_f64()
+                .map(FieldValue::Float)
+                .unwrap_or_else(|| FieldValue::Raw(value.clone())),
+            "Bool" => value
+                .as_bool()
+                .map(FieldValue::Bool)
+                .unwrap_or_else(|| FieldValue::Raw(value.clone())),
+            "String" | "Multilines" | "FilePath" => {
+                FieldValue::String(value.as_str().map(str::to_owned))
+            }
+            "Color" => match value.as_str().and_then(parse_hex_color) {
+                Some(color) => FieldValue::Color(color),
+                None => FieldValue::Raw(value.clone()),
+            },
+            "Point" => match (
+                value.get("cx").and_then(|v| v.as_i64()),
+                value.get("cy").and_then(|v| v.as_i64()),
+            ) {
+                (Some(cx), Some(cy)) => FieldValue::Point([cx, cy]),
+                _ => FieldValue::Raw(value.clone()),
+            },
+            "EntityRef" => match value.get("entityIid").and_then(|v| v.as_str()) {
+                Some(iid) => FieldValue::EntityRef(iid.to_owned()),
+                None => FieldValue::Raw(value.clone()),
+            },
+            // Enum field types are identified by the enum's own name (e.g. `LocalEnum.Direction`),
+            // not a fixed string, so match any type name containing `Enum`.
+            _ if field_type.contains("Enum") => match value.as_str() {
+                Some(s) => FieldValue::EnumValue(s.to_owned()),
+                None => FieldValue::Raw(value.clone()),
+            },
+            _ => FieldValue::Raw(value.clone()),
+        }
+    }
+
+    /// Parses a `#rrggbb` hex color string into RGBA bytes, with alpha fixed at `255`.
+    pub fn parse_hex_color(hex: &str) -> Option<[u8; 4]> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if hex.len() != 6 {
+            return None;
         }
+
+        let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+        let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+        let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+
+        Some([r, g, b, 255])
     }
 
     /// Converts LayerDefinition to an LdtkLayerDef.
     pub fn convert_layer_def(input: &LayerDefinition) -> Result<LdtkLayerDef, Error> {
         let layer_type = convert_layer_type(&input.layer_definition_type)?;
 
+        let int_grid_colors = input
+            .int_grid_values
+            .iter()
+            .filter_map(|v| parse_hex_color(&v.color).map(|color| (v.value, color)))
+            .collect();
+
         let layerdef = LdtkLayerDef {
             layer_type,
             identifier: input.identifier.clone(),
             opacity: input.display_opacity,
             grid_size: input.grid_size,
             uid: input.uid,
+            int_grid_colors,
         };
 
         Ok(layerdef)
     }
 
-    /// Converts a Level into an LdtkLevel.
-    pub fn convert_level(input: &Level) -> LdtkLevel {
+    /// Converts a Level into an LdtkLevel. `layer_instances` is resolved ahead of time by the
+    /// caller, since it may need to be loaded from an external `.ldtkl` file.
+    pub fn convert_level(input: &Level, layer_instances: &[LayerInstance]) -> LdtkLevel {
         let mut layer_insts: Vec<LdtkLayerInstance> = Vec::new();
 
-        for l in input.layer_instances.as_ref().unwrap() {
+        for l in layer_instances {
             let source_tiles = if l.grid_tiles.len() > 0 {
                 &l.grid_tiles
             } else {
@@ -201,10 +350,31 @@ mod convert {
             layer_insts.push(l_converted);
         }
 
+        let bg_color = parse_hex_color(&input.bg_color)
+            .map(|c| Color::from_rgba(c[0], c[1], c[2], c[3]))
+            .unwrap_or(BLACK);
+
+        let bg_image = input
+            .bg_rel_path
+            .as_ref()
+            .zip(input.bg_pos.as_ref())
+            .map(|(rel_path, bg_pos)| LdtkLevelBg {
+                rel_path: rel_path.clone(),
+                top_left_px: bg_pos.top_left_px,
+                crop: bg_pos.crop_rect,
+                scale: bg_pos.scale,
+            });
+
         LdtkLevel {
             layers: layer_insts,
             width: input.px_wid,
             height: input.px_hei,
+            iid: input.iid.clone(),
+            world_x: input.world_x,
+            world_y: input.world_y,
+            neighbour_iids: input.neighbours.iter().map(|n| n.level_iid.clone()).collect(),
+            bg_color,
+            bg_image,
         }
     }
 }
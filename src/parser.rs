@@ -0,0 +1,170 @@
+//! Raw `serde` representations of the LDtk JSON format.
+//!
+//! These mirror the on-disk schema as closely as possible. They are not meant to be
+//! consumed directly by users of the crate; see [`crate::types`] for the trimmed-down,
+//! render-ready structures produced by [`crate::load::load_project`].
+
+use serde::Deserialize;
+
+/// Root of an LDtk project file.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LdtkJson {
+    pub defs: Definitions,
+    pub levels: Vec<Level>,
+    pub world_layout: Option<WorldLayout>,
+    /// `true` when the project was saved with "Save levels to separate files";
+    /// each level's `layer_instances` is then `None` and must be loaded from `external_rel_path`.
+    pub external_levels: bool,
+}
+
+/// Definitions section of the project, holding tileset and layer metadata.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Definitions {
+    pub layers: Vec<LayerDefinition>,
+    pub tilesets: Vec<TilesetDefinition>,
+}
+
+/// A tileset as defined in the editor, before being matched up with a loaded texture.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TilesetDefinition {
+    pub identifier: String,
+    pub uid: i64,
+    pub rel_path: Option<String>,
+    pub c_hei: i64,
+    pub c_wid: i64,
+    pub padding: i64,
+    pub spacing: i64,
+    pub tile_grid_size: i64,
+}
+
+/// A layer as defined in the editor, shared across all of its instances.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LayerDefinition {
+    pub identifier: String,
+    pub uid: i64,
+    #[serde(rename = "type")]
+    pub layer_definition_type: String,
+    pub display_opacity: f64,
+    pub grid_size: i64,
+    pub int_grid_values: Vec<IntGridValueDefinition>,
+}
+
+/// A single named value in an IntGrid layer's legend, with the color the editor renders it as.
+#[derive(Deserialize, Debug)]
+pub struct IntGridValueDefinition {
+    pub value: i64,
+    pub identifier: Option<String>,
+    pub color: String,
+}
+
+/// The layout used to arrange levels within the world.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldLayout {
+    Free,
+    GridVania,
+    LinearHorizontal,
+    LinearVertical,
+}
+
+/// A single level, as laid out in the project (or an external level file).
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Level {
+    pub identifier: String,
+    pub iid: String,
+    pub world_x: i64,
+    pub world_y: i64,
+    pub px_wid: i64,
+    pub px_hei: i64,
+    pub layer_instances: Option<Vec<LayerInstance>>,
+    /// Path to the `.ldtkl` file holding this level's data, relative to the project file.
+    /// Only set when the project has "Save levels to separate files" enabled.
+    pub external_rel_path: Option<String>,
+    #[serde(rename = "__neighbours")]
+    pub neighbours: Vec<NeighbourLevel>,
+    pub bg_color: String,
+    pub bg_rel_path: Option<String>,
+    #[serde(rename = "__bgPos")]
+    pub bg_pos: Option<BgPos>,
+}
+
+/// Crop and placement data for a level's background image, as computed by the editor.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BgPos {
+    pub top_left_px: [f64; 2],
+    pub crop_rect: [f64; 4],
+    pub scale: [f64; 2],
+}
+
+/// A level touching another level's edge, as computed by the editor.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NeighbourLevel {
+    pub level_iid: String,
+    pub dir: String,
+}
+
+/// An instance of a layer within a level, holding the actual tile/entity/IntGrid data.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LayerInstance {
+    pub identifier: String,
+    pub c_hei: i64,
+    pub c_wid: i64,
+    pub grid_size: i64,
+    pub tileset_rel_path: Option<String>,
+    pub int_grid_csv: Vec<i64>,
+    pub entity_instances: Vec<EntityInstance>,
+    pub grid_tiles: Vec<TileInstance>,
+    pub auto_layer_tiles: Vec<TileInstance>,
+}
+
+/// A single placed tile, referencing a position both in the level and in the source tileset.
+#[derive(Deserialize, Debug, Clone)]
+pub struct TileInstance {
+    /// Alpha/opacity, from 0 to 1.
+    pub a: f64,
+    /// Flip bitfield: bit `0b01` is a horizontal flip, bit `0b10` is a vertical flip.
+    pub f: i64,
+    /// Coordinates in the level, in pixels.
+    pub px: [i64; 2],
+    /// Coordinates in the source tileset image, in pixels.
+    pub src: [i64; 2],
+    /// Tile ID in the corresponding tileset.
+    pub t: i64,
+}
+
+/// An entity placed in the level, as saved by the editor.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityInstance {
+    pub grid: [i64; 2],
+    pub identifier: String,
+    pub iid: String,
+    pub pivot: [f64; 2],
+    pub tags: Vec<String>,
+    pub px: [i64; 2],
+    #[serde(rename = "__worldX")]
+    pub world_x: Option<i64>,
+    #[serde(rename = "__worldY")]
+    pub world_y: Option<i64>,
+    pub width: i64,
+    pub height: i64,
+    pub field_instances: Vec<FieldInstance>,
+}
+
+/// A single custom field value attached to an entity instance.
+#[derive(Deserialize, Debug, Clone)]
+pub struct FieldInstance {
+    #[serde(rename = "__identifier")]
+    pub identifier: String,
+    #[serde(rename = "__type")]
+    pub field_type: String,
+    #[serde(rename = "__value")]
+    pub value: serde_json::Value,
+}
@@ -2,6 +2,8 @@
 
 use std::collections::HashMap;
 
+use macroquad::color::Color;
+
 /// Struct that holds all necessary resources from an LDtk project.
 /// Does not hold all data from the project, only what is needed for its own methods.
 pub struct LdtkResources {
@@ -19,6 +21,36 @@ pub struct LdtkLevel {
     pub width: i64,
     pub height: i64,
     pub layers: Vec<LdtkLayerInstance>,
+
+    /// Unique instance identifier.
+    pub iid: String,
+
+    /// World-space pixel origin of this level, copied as-is from the project file. Only
+    /// meaningful in Free and GridVania world layouts.
+    pub world_x: i64,
+    pub world_y: i64,
+
+    /// `iid`s of levels touching this one's edges, as computed by the editor.
+    pub neighbour_iids: Vec<String>,
+
+    /// The level's background color, as set (or defaulted) in the editor.
+    pub bg_color: Color,
+
+    /// The level's background image, if one is set.
+    pub bg_image: Option<LdtkLevelBg>,
+}
+
+/// Crop and placement data for a level's background image, mirroring how the editor composites
+/// it: the source image is cropped to `crop`, scaled by `scale`, then placed at `top_left_px`.
+pub struct LdtkLevelBg {
+    /// Path to the background image, relative to the project file.
+    pub rel_path: String,
+    /// Top-left position of the placed image within the level, in pixels.
+    pub top_left_px: [f64; 2],
+    /// Crop rectangle within the source image, as `[x, y, width, height]` in pixels.
+    pub crop: [f64; 4],
+    /// Scale applied to the cropped image before placement.
+    pub scale: [f64; 2],
 }
 
 /// Extra layer data, such as opacity
@@ -29,6 +61,9 @@ pub struct LdtkLayerDef {
     pub grid_size: i64,
 
     pub uid: i64,
+
+    /// Maps each IntGrid value to the `RGBA` color the editor renders it as.
+    pub int_grid_colors: HashMap<i64, [u8; 4]>,
 }
 
 /// Instances of a layer that hold actual terrain data
@@ -78,6 +113,94 @@ pub struct LdtkEntityInstance {
 
     pub height: i64,
     pub width: i64,
+
+    /// Custom field values, keyed by field identifier.
+    pub fields: HashMap<String, FieldValue>,
+}
+
+impl LdtkEntityInstance {
+    /// Gets a custom field as an `Int`, if it exists and is of that type.
+    pub fn get_int(&self, identifier: &str) -> Option<i64> {
+        match self.fields.get(identifier) {
+            Some(FieldValue::Int(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Gets a custom field as a `Float`, if it exists and is of that type.
+    pub fn get_float(&self, identifier: &str) -> Option<f64> {
+        match self.fields.get(identifier) {
+            Some(FieldValue::Float(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Gets a custom field as a `Bool`, if it exists and is of that type.
+    pub fn get_bool(&self, identifier: &str) -> Option<bool> {
+        match self.fields.get(identifier) {
+            Some(FieldValue::Bool(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Gets a custom field as a `String`, if it exists and is of that type and set.
+    pub fn get_string(&self, identifier: &str) -> Option<&str> {
+        match self.fields.get(identifier) {
+            Some(FieldValue::String(v)) => v.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Gets a custom field as a `Point`, if it exists and is of that type.
+    pub fn get_point(&self, identifier: &str) -> Option<[i64; 2]> {
+        match self.fields.get(identifier) {
+            Some(FieldValue::Point(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Gets a custom field as a `Color`, if it exists and is of that type.
+    pub fn get_color(&self, identifier: &str) -> Option<[u8; 4]> {
+        match self.fields.get(identifier) {
+            Some(FieldValue::Color(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Gets a custom field as an `EnumValue`, if it exists and is of that type.
+    pub fn get_enum(&self, identifier: &str) -> Option<&str> {
+        match self.fields.get(identifier) {
+            Some(FieldValue::EnumValue(v)) => Some(v.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Gets a custom field as an `EntityRef`, returning the referenced entity's `iid`.
+    pub fn get_entity_ref(&self, identifier: &str) -> Option<&str> {
+        match self.fields.get(identifier) {
+            Some(FieldValue::EntityRef(v)) => Some(v.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// A single custom field value, as defined by the entity's field definitions in the editor.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(Option<String>),
+    /// `RGBA`, decoded from the editor's `#rrggbb` hex string with alpha set to `255`.
+    Color([u8; 4]),
+    Point([i64; 2]),
+    /// Name of the selected enum value.
+    EnumValue(String),
+    /// `iid` of the referenced entity.
+    EntityRef(String),
+    Array(Vec<FieldValue>),
+    /// Fallback for field types not covered above, so unrecognized/future types don't panic.
+    Raw(serde_json::Value),
 }
 
 /// Holds the data for a tileset.
@@ -106,6 +229,16 @@ pub struct LdtkTileInstance {
     pub src_coords: [i64; 2],
 
     pub tile_id: i64,
+
+    /// Whether the tile is flipped on the X and/or Y axis.
+    pub flip: LdtkTileFlip,
+}
+
+/// Flip state of a tile, decoded from LDtk's `f` bitfield (`0b01` = X, `0b10` = Y).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LdtkTileFlip {
+    pub x: bool,
+    pub y: bool,
 }
 
 /// Layer types selectable in the LDtk editor